@@ -0,0 +1,57 @@
+use crate::fs::erdtree::{disk_usage::DiskUsage, order::Order, render::OutputFormat};
+use clap::Parser;
+use ignore::{WalkBuilder, WalkParallel};
+use std::path::{Path, PathBuf};
+
+/// Command-line arguments and flags, the single source of configuration for a run of `erdtree`.
+#[derive(Parser, Debug)]
+#[command(name = "erdtree", author, version, about = "Visualize the directory structure")]
+pub struct Context {
+    /// Root directory to traverse; defaults to the current directory
+    #[arg(default_value = ".")]
+    pub dir: PathBuf,
+
+    /// Sort-order to display directory contents
+    #[arg(short, long, value_enum, default_value = "none")]
+    pub order: Order,
+
+    /// Maximum depth to display
+    #[arg(short = 'L', long)]
+    pub level: Option<usize>,
+
+    /// Count the size of a file that has multiple hard links more than once
+    #[arg(long)]
+    pub count_hard_links: bool,
+
+    /// Whether to report apparent file size or actual space allocated on disk
+    #[arg(long, value_enum, default_value = "logical")]
+    pub disk_usage: DiskUsage,
+
+    /// Don't descend into directories that live on a different filesystem than the root
+    #[arg(long = "one-file-system")]
+    pub same_file_system: bool,
+
+    /// Print live progress (entries and bytes scanned) to stderr while traversing
+    #[arg(long)]
+    pub progress: bool,
+
+    /// Annotate each entry with its git status
+    #[arg(long)]
+    pub git: bool,
+
+    /// Presentation format for the assembled tree
+    #[arg(long, value_enum, default_value = "tree")]
+    pub output: OutputFormat,
+}
+
+impl Context {
+    /// Root directory this [Context] was configured to traverse.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Builds the parallel walker used to traverse [`Context::dir`], respecting `.gitignore`.
+    pub fn walker(&self) -> WalkParallel {
+        WalkBuilder::new(&self.dir).build_parallel()
+    }
+}
@@ -0,0 +1,43 @@
+use clap::ValueEnum;
+use std::{fs::Metadata, path::Path};
+
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+
+#[cfg(windows)]
+use filesize::PathExt;
+
+/// Which size metric to report for a file: the logical byte length, or the physical space it
+/// occupies on disk.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum DiskUsage {
+    /// Apparent size: the byte length of the file's contents.
+    Logical,
+
+    /// Size-on-disk: blocks actually allocated for the file, which can be smaller than the
+    /// apparent size for sparse files or larger once rounded up to the filesystem's block size.
+    Physical,
+}
+
+impl DiskUsage {
+    /// Computes the size of `path`/`metadata` according to the selected metric, in bytes.
+    pub fn size_of(&self, path: &Path, metadata: &Metadata) -> u64 {
+        match self {
+            Self::Logical => metadata.len(),
+
+            #[cfg(unix)]
+            Self::Physical => metadata.blocks() * 512,
+
+            // Windows has no `blocks`-equivalent on `Metadata`; `filesize` asks the filesystem
+            // for the cluster-rounded allocation size instead, falling back to the apparent size
+            // if that query fails (e.g. the file vanished between readdir and stat).
+            #[cfg(windows)]
+            Self::Physical => path
+                .size_on_disk_fast(metadata)
+                .unwrap_or_else(|_| metadata.len()),
+
+            #[cfg(not(any(unix, windows)))]
+            Self::Physical => metadata.len(),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use git2::{Repository, Status, StatusOptions};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+/// Simplified view of a path's `git status`, collapsed down to the single marker erdtree renders
+/// next to the entry.
+///
+/// There's deliberately no `Ignored` variant: the default walker (see [`Context::walker`])
+/// already respects `.gitignore` and never yields ignored entries as `Node`s, so such a status
+/// could never be attached to anything.
+///
+/// [`Context::walker`]: crate::context::Context::walker
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GitStatus {
+    /// Tracked and modified relative to `HEAD`.
+    Modified,
+
+    /// Staged but not yet committed.
+    Added,
+
+    /// Tracked but missing from the working tree.
+    Deleted,
+
+    /// Not tracked by git.
+    Untracked,
+}
+
+impl GitStatus {
+    /// Colored single-character glyph used when annotating a tree entry.
+    pub fn marker(&self) -> &'static str {
+        match self {
+            Self::Modified => "\x1b[33mM\x1b[0m",
+            Self::Added => "\x1b[32mA\x1b[0m",
+            Self::Deleted => "\x1b[31mD\x1b[0m",
+            Self::Untracked => "\x1b[36m?\x1b[0m",
+        }
+    }
+
+    fn from_raw(status: Status) -> Option<Self> {
+        if status.is_wt_new() {
+            Some(Self::Untracked)
+        } else if status.is_index_new() {
+            Some(Self::Added)
+        } else if status.is_wt_deleted() || status.is_index_deleted() {
+            Some(Self::Deleted)
+        } else if status.is_wt_modified() || status.is_index_modified() {
+            Some(Self::Modified)
+        } else {
+            None
+        }
+    }
+}
+
+/// Opens the git repository containing `root`, if any, and collects the status of every
+/// tracked/untracked path within it. Returns the repository's canonicalized workdir alongside a
+/// map keyed by path *relative to that workdir* (not joined into an absolute path, since what
+/// counts as "relative" on the caller's side depends on where the walker was rooted). Returns an
+/// empty map when `root` isn't inside a git work tree; this is pure post-processing and never
+/// touches the traversal phase. Ignored paths are never requested, since nothing can match them.
+pub fn collect_statuses(root: &Path) -> (PathBuf, HashMap<PathBuf, GitStatus>) {
+    let repo = match Repository::discover(root) {
+        Ok(repo) => repo,
+        Err(_) => return (PathBuf::new(), HashMap::new()),
+    };
+
+    let workdir = match repo.workdir() {
+        Some(workdir) => workdir,
+        None => return (PathBuf::new(), HashMap::new()),
+    };
+
+    let workdir = workdir.canonicalize().unwrap_or_else(|_| workdir.to_path_buf());
+
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true).recurse_untracked_dirs(true);
+
+    let statuses = match repo.statuses(Some(&mut opts)) {
+        Ok(statuses) => statuses,
+        Err(_) => return (workdir, HashMap::new()),
+    };
+
+    let by_path = statuses
+        .iter()
+        .filter_map(|entry| {
+            let path = entry.path()?;
+            let status = GitStatus::from_raw(entry.status())?;
+            Some((PathBuf::from(path), status))
+        })
+        .collect();
+
+    (workdir, by_path)
+}
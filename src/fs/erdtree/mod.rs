@@ -0,0 +1,7 @@
+pub mod disk_usage;
+pub mod git;
+pub mod node;
+pub mod order;
+pub mod progress;
+pub mod render;
+pub mod tree;
@@ -0,0 +1,171 @@
+use super::{disk_usage::DiskUsage, git::GitStatus};
+use ignore::DirEntry;
+use std::{
+    fmt::{self, Display, Formatter},
+    fs::{FileType, Metadata},
+    path::{Path, PathBuf},
+    slice::Iter,
+};
+
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+
+/// A single filesystem entry discovered during traversal, along with whatever children have
+/// since been attached to it by [`Tree::assemble_tree`].
+///
+/// [`Tree::assemble_tree`]: super::tree::Tree::assemble_tree
+#[derive(Debug)]
+pub struct Node {
+    path: PathBuf,
+
+    /// Depth of this entry relative to the root of the traversal.
+    pub depth: usize,
+
+    /// Size in bytes, measured according to whichever [`DiskUsage`] was in effect during
+    /// traversal; for directories this is replaced with the aggregated size of their contents
+    /// once [`Tree::assemble_tree`] has run, unless that aggregate is zero, in which case the
+    /// directory keeps its own measured size.
+    ///
+    /// [`Tree::assemble_tree`]: super::tree::Tree::assemble_tree
+    pub file_size: Option<u64>,
+
+    /// `(device, inode)` pair identifying the entry on disk, used to detect hard links.
+    pub inode: Option<(u64, u64)>,
+
+    /// Number of hard links reported for the entry.
+    pub links: u64,
+
+    /// Recursive count of files and subdirectories contained within this entry; `None` until
+    /// [`Tree::assemble_tree`] has filled it in, and always `None` for non-directories.
+    ///
+    /// [`Tree::assemble_tree`]: super::tree::Tree::assemble_tree
+    pub entry_count: Option<u64>,
+
+    /// Git status of this entry, attached as post-processing once traversal has finished; `None`
+    /// when `--git` wasn't requested or the entry has no notable status.
+    pub git_status: Option<GitStatus>,
+
+    file_type: Option<FileType>,
+    children: Option<Vec<Node>>,
+}
+
+impl Node {
+    /// Builds a [Node] from a traversal entry, measuring its size according to `disk_usage`.
+    ///
+    /// `metadata` is taken in rather than fetched here so callers that already needed it for
+    /// their own purposes (e.g. a cross-device check) don't end up stat-ing the entry twice.
+    pub fn new(entry: DirEntry, metadata: Option<Metadata>, disk_usage: DiskUsage) -> Self {
+        let file_type = entry.file_type();
+        let depth = entry.depth();
+        let file_size = metadata
+            .as_ref()
+            .map(|md| disk_usage.size_of(entry.path(), md));
+
+        #[cfg(unix)]
+        let (inode, links) = metadata
+            .as_ref()
+            .map(|md| (Some((md.dev(), md.ino())), md.nlink()))
+            .unwrap_or((None, 1));
+
+        #[cfg(not(unix))]
+        let (inode, links) = (None, 1);
+
+        Self {
+            path: entry.into_path(),
+            depth,
+            file_size,
+            inode,
+            links,
+            entry_count: None,
+            git_status: None,
+            file_type,
+            children: None,
+        }
+    }
+
+    /// Returns a reference to the [Node]'s path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the path of the [Node]'s parent, if any.
+    pub fn parent_path_buf(&self) -> Option<PathBuf> {
+        self.path.parent().map(Path::to_path_buf)
+    }
+
+    /// Whether the [Node] represents a directory.
+    pub fn is_dir(&self) -> bool {
+        self.file_type.map(|ft| ft.is_dir()).unwrap_or(false)
+    }
+
+    /// Whether the [Node] is a non-directory entry with more than one hard link pointing to its
+    /// inode.
+    ///
+    /// Directories routinely report `nlink >= 2` on Unix (one for `.` plus one per subdirectory),
+    /// so they're deliberately excluded here to match the hard-link dedup use case.
+    pub fn is_hard_link(&self) -> bool {
+        !self.is_dir() && self.links > 1
+    }
+
+    /// Attaches `children` to the [Node].
+    pub fn set_children(&mut self, children: Vec<Node>) {
+        self.children = Some(children);
+    }
+
+    /// Overwrites the [Node]'s aggregated file size.
+    pub fn set_file_size(&mut self, size: u64) {
+        self.file_size = Some(size);
+    }
+
+    /// Overwrites the [Node]'s recursive entry count.
+    pub fn set_entry_count(&mut self, count: u64) {
+        self.entry_count = Some(count);
+    }
+
+    /// Attaches a git status to the [Node].
+    pub fn set_git_status(&mut self, status: GitStatus) {
+        self.git_status = Some(status);
+    }
+
+    /// Returns an iterator over the [Node]'s children, if any have been attached.
+    pub fn children(&self) -> Option<Iter<Node>> {
+        self.children.as_ref().map(|children| children.iter())
+    }
+
+    /// Returns a mutable reference to the [Node]'s children, if any have been attached.
+    pub fn children_mut(&mut self) -> Option<&mut Vec<Node>> {
+        self.children.as_mut()
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_else(|| self.path.to_string_lossy());
+
+        if let Some(status) = &self.git_status {
+            write!(f, "{} ", status.marker())?;
+        }
+
+        write!(f, "{name}")?;
+
+        let mut annotations = Vec::new();
+
+        if let Some(size) = self.file_size {
+            annotations.push(format!("{size}B"));
+        }
+
+        if let Some(count) = self.entry_count {
+            annotations.push(format!("{count} entries"));
+        }
+
+        if !annotations.is_empty() {
+            write!(f, " ({})", annotations.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
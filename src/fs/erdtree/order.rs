@@ -0,0 +1,61 @@
+use super::node::Node;
+use clap::ValueEnum;
+use std::cmp::Ordering;
+
+/// Ordering to apply to sibling directory entries once the [Tree] has been assembled.
+///
+/// [Tree]: super::tree::Tree
+#[derive(Clone, Debug, ValueEnum)]
+pub enum Order {
+    /// Preserve the order entries were yielded by the filesystem walker.
+    None,
+
+    /// Sort by file size, smallest to largest.
+    Size,
+
+    /// Sort by file size, largest to smallest.
+    SizeRev,
+
+    /// Sort directories by their recursive entry count, fewest to most.
+    EntryCount,
+
+    /// Sort directories by their recursive entry count, most to fewest.
+    EntryCountRev,
+
+    /// Surface entries with a non-clean `--git` status before everything else.
+    GitChanges,
+}
+
+impl Order {
+    /// Returns the comparator function associated with the variant, if any.
+    pub fn comparator(&self) -> Option<fn(&Node, &Node) -> Ordering> {
+        match self {
+            Self::None => None,
+            Self::Size => Some(Self::cmp_size),
+            Self::SizeRev => Some(Self::cmp_size_rev),
+            Self::EntryCount => Some(Self::cmp_entry_count),
+            Self::EntryCountRev => Some(Self::cmp_entry_count_rev),
+            Self::GitChanges => Some(Self::cmp_git_changes),
+        }
+    }
+
+    fn cmp_size(a: &Node, b: &Node) -> Ordering {
+        a.file_size.unwrap_or(0).cmp(&b.file_size.unwrap_or(0))
+    }
+
+    fn cmp_size_rev(a: &Node, b: &Node) -> Ordering {
+        Self::cmp_size(a, b).reverse()
+    }
+
+    fn cmp_entry_count(a: &Node, b: &Node) -> Ordering {
+        a.entry_count.unwrap_or(0).cmp(&b.entry_count.unwrap_or(0))
+    }
+
+    fn cmp_entry_count_rev(a: &Node, b: &Node) -> Ordering {
+        Self::cmp_entry_count(a, b).reverse()
+    }
+
+    fn cmp_git_changes(a: &Node, b: &Node) -> Ordering {
+        b.git_status.is_some().cmp(&a.git_status.is_some())
+    }
+}
@@ -0,0 +1,72 @@
+use std::{
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// Rate-limits how often something may fire, so a hot loop doesn't spend its time repainting a
+/// status line on every iteration.
+struct Throttle {
+    last_emit: Mutex<Instant>,
+    interval: Duration,
+}
+
+impl Throttle {
+    fn new(interval: Duration) -> Self {
+        Self { last_emit: Mutex::new(Instant::now()), interval }
+    }
+
+    /// Returns `true` at most once per `interval`.
+    fn ready(&self) -> bool {
+        let mut last_emit = self.last_emit.lock().unwrap();
+        let now = Instant::now();
+
+        if now.duration_since(*last_emit) >= self.interval {
+            *last_emit = now;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Tracks how many entries and bytes have been seen so far during traversal and periodically
+/// emits a single-line status to stderr. Cheap to update from a hot loop: counters are atomic
+/// and the expensive part (writing to stderr) is throttled.
+pub struct Progress {
+    entries: AtomicU64,
+    bytes: AtomicU64,
+    throttle: Throttle,
+}
+
+impl Progress {
+    /// Creates a [Progress] that emits at most once per `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            entries: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            throttle: Throttle::new(interval),
+        }
+    }
+
+    /// Records a single traversed entry of `size` bytes, emitting a status line if the throttle
+    /// allows it.
+    pub fn record(&self, size: u64) {
+        let entries = self.entries.fetch_add(1, Ordering::Relaxed) + 1;
+        let bytes = self.bytes.fetch_add(size, Ordering::Relaxed) + size;
+
+        if self.throttle.ready() {
+            eprint!("\rscanned {entries} entries, {bytes} bytes");
+            let _ = io::stderr().flush();
+        }
+    }
+
+    /// Clears the status line left behind by [`Progress::record`].
+    pub fn clear(&self) {
+        eprint!("\r\x1b[2K");
+        let _ = io::stderr().flush();
+    }
+}
@@ -0,0 +1,100 @@
+use super::{node::Node, tree::Tree};
+use clap::ValueEnum;
+use serde_json::{json, Value};
+use std::io::{self, Write};
+
+/// Which presentation format to render a [Tree] in.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum OutputFormat {
+    /// ANSI box-drawing tree.
+    Tree,
+
+    /// A single nested JSON document.
+    Json,
+
+    /// Newline-delimited JSON, one flat record per entry.
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Returns the [Renderer] for this format.
+    pub fn renderer(&self) -> Box<dyn Renderer> {
+        match self {
+            Self::Tree => Box::new(TreeRenderer),
+            Self::Json => Box::new(JsonRenderer),
+            Self::Ndjson => Box::new(NdjsonRenderer),
+        }
+    }
+}
+
+/// A presentation strategy for a fully assembled [Tree]. Decouples the `Node`/`Tree` data model
+/// from any one presentation so new output backends can be added without touching traversal.
+pub trait Renderer {
+    /// Writes `tree` to `writer` in this renderer's format.
+    fn render(&self, tree: &Tree, writer: &mut dyn Write) -> io::Result<()>;
+}
+
+/// Renders the tree as ANSI box-drawing text, reusing [Tree]'s `Display` impl.
+pub struct TreeRenderer;
+
+impl Renderer for TreeRenderer {
+    fn render(&self, tree: &Tree, writer: &mut dyn Write) -> io::Result<()> {
+        write!(writer, "{tree}")
+    }
+}
+
+/// Renders the tree as a single nested JSON document: each node is an object with `path`,
+/// `depth`, `is_dir`, `size`, `entry_count`, and a `children` array.
+pub struct JsonRenderer;
+
+impl Renderer for JsonRenderer {
+    fn render(&self, tree: &Tree, writer: &mut dyn Write) -> io::Result<()> {
+        writeln!(writer, "{}", node_to_json(tree.root()))
+    }
+}
+
+/// Renders the tree as newline-delimited JSON: one flat record per entry, with no `children`
+/// array, suitable for streaming into tools like `jq`.
+pub struct NdjsonRenderer;
+
+impl Renderer for NdjsonRenderer {
+    fn render(&self, tree: &Tree, writer: &mut dyn Write) -> io::Result<()> {
+        write_ndjson(tree.root(), writer)
+    }
+}
+
+fn node_to_json(node: &Node) -> Value {
+    let children: Vec<Value> = node
+        .children()
+        .map(|children| children.map(node_to_json).collect())
+        .unwrap_or_default();
+
+    json!({
+        "path": node.path(),
+        "depth": node.depth,
+        "is_dir": node.is_dir(),
+        "size": node.file_size,
+        "entry_count": node.entry_count,
+        "children": children,
+    })
+}
+
+fn write_ndjson(node: &Node, writer: &mut dyn Write) -> io::Result<()> {
+    let record = json!({
+        "path": node.path(),
+        "depth": node.depth,
+        "is_dir": node.is_dir(),
+        "size": node.file_size,
+        "entry_count": node.entry_count,
+    });
+
+    writeln!(writer, "{record}")?;
+
+    if let Some(children) = node.children() {
+        for child in children {
+            write_ndjson(child, writer)?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,18 +1,26 @@
 use super::order::Order;
+use crate::context::Context;
 use crossbeam::channel::{self, Sender};
 use ignore::{WalkParallel, WalkState};
 use std::{
     collections::HashMap,
     fmt::{self, Display, Formatter},
-    path::PathBuf,
+    path::{Path, PathBuf},
     slice::Iter,
+    sync::Arc,
     thread,
+    time::Duration,
 };
 use super::{
+    git::{self, GitStatus},
     node::Node,
+    progress::Progress,
     super::error::Error
 };
 
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+
 #[cfg(test)]
 mod test;
 
@@ -42,11 +50,27 @@ pub type Branches = HashMap::<PathBuf, Vec<Node>>;
 pub type TreeComponents = (Node, Branches);
 
 impl Tree {
-    /// Initializes a [Tree].
-    pub fn new(walker: WalkParallel, order: Order, max_depth: Option<usize>) -> TreeResult<Self> {
-        let root = Self::traverse(walker, &order)?;
+    /// Initializes a [Tree] according to the flags set on `ctx`.
+    pub fn new(walker: WalkParallel, ctx: &Context) -> TreeResult<Self> {
+        let mut root = Self::traverse(walker, ctx)?;
+
+        if ctx.git {
+            let (workdir, statuses) = git::collect_statuses(ctx.dir());
+
+            // Computed once (not per node) so annotation can rebase each node's path onto the
+            // repo-relative paths `statuses` is keyed by using pure string manipulation, with no
+            // extra stat syscalls.
+            let repo_relative_root = ctx
+                .dir()
+                .canonicalize()
+                .ok()
+                .and_then(|canonical| canonical.strip_prefix(&workdir).ok().map(Path::to_path_buf))
+                .unwrap_or_default();
+
+            Self::annotate_git_status(&mut root, ctx.dir(), &repo_relative_root, &statuses, &ctx.order);
+        }
 
-        Ok(Self { max_depth, order, root })
+        Ok(Self { max_depth: ctx.level, order: ctx.order.clone(), root })
     }
 
     /// Returns a reference to the root [Node].
@@ -59,45 +83,73 @@ impl Tree {
     /// system calls are expected to occur during parallel traversal; thus post-processing of all
     /// directory entries should be completely CPU-bound. If filesystem I/O or system calls occur
     /// outside of the parallel traversal step please report an issue.
-    fn traverse(walker: WalkParallel, order: &Order) -> TreeResult<Node> {
+    fn traverse(walker: WalkParallel, ctx: &Context) -> TreeResult<Node> {
+        let order = &ctx.order;
+        let count_hard_links = ctx.count_hard_links;
+        let disk_usage = ctx.disk_usage;
+
+        // Captured up-front (mirrors dua's `crossdev` handling) so every worker thread can cheaply
+        // compare an entry's device id against it without any extra coordination.
+        #[cfg(unix)]
+        let root_dev = ctx.same_file_system
+            .then(|| std::fs::metadata(ctx.dir()).ok().map(|md| md.dev()))
+            .flatten();
+
+        // `--one-file-system` is a silent no-op off Unix: there's no portable way to compare
+        // device ids, so nothing is ever treated as crossing a filesystem boundary.
+        #[cfg(not(unix))]
+        let root_dev: Option<u64> = None;
+
+        let progress = ctx
+            .progress
+            .then(|| Arc::new(Progress::new(Duration::from_millis(100))));
+
         let (tx, rx) = channel::unbounded::<Node>();
 
         // Receives directory entries from the workers used for parallel traversal to construct the
         // components needed to assmemble a `Tree`.
-        let tree_components = thread::spawn(move || -> TreeResult<TreeComponents> {
-            let mut branches: Branches = HashMap::new();
-            let mut root = None;
+        let tree_components = thread::spawn({
+            let progress = progress.clone();
 
-            while let Ok(node) = rx.recv() {
-                if node.is_dir() {
-                    let node_path = node.path();
+            move || -> TreeResult<TreeComponents> {
+                let mut branches: Branches = HashMap::new();
+                let mut root = None;
 
-                    if !branches.contains_key(node_path) {
-                        branches.insert(node_path.to_owned(), vec![]);
+                while let Ok(node) = rx.recv() {
+                    if let Some(progress) = &progress {
+                        progress.record(node.file_size.unwrap_or(0));
                     }
 
-                    if node.depth == 0 {
-                        root = Some(node);
-                        continue;
+                    if node.is_dir() {
+                        let node_path = node.path();
+
+                        if !branches.contains_key(node_path) {
+                            branches.insert(node_path.to_owned(), vec![]);
+                        }
+
+                        if node.depth == 0 {
+                            root = Some(node);
+                            continue;
+                        }
                     }
-                }
 
-                let parent = node
-                    .parent_path_buf()
-                    .ok_or(Error::ExpectedParent)?;
+                    let parent = node
+                        .parent_path_buf()
+                        .ok_or(Error::ExpectedParent)?;
 
-                let update = branches
-                    .get_mut(&parent)
-                    .map(|mut_ref| mut_ref.push(node));
+                    let update = branches
+                        .get_mut(&parent)
+                        .map(|mut_ref| mut_ref.push(node));
 
-                if let None = update {
-                    branches.insert(parent, vec![]);
+                    if let None = update {
+                        branches.insert(parent, vec![]);
+                    }
                 }
-            }
 
-            let root_node = root.ok_or(Error::MissingRoot)?;
+                let root_node = root.ok_or(Error::MissingRoot)?;
 
-            Ok((root_node, branches))
+                Ok((root_node, branches))
+            }
         });
 
         // All filesystem I/O and related system-calls should be relegated to this. Directory
@@ -105,25 +157,65 @@ impl Tree {
         walker.run(|| Box::new(|entry_res| {
             let tx = Sender::clone(&tx);
 
-            entry_res
-                .map(|entry| Node::from(entry)) 
-                .map(|node| tx.send(node).unwrap())
-                .map(|_| WalkState::Continue)
-                .unwrap_or(WalkState::Skip)
+            let entry = match entry_res {
+                Ok(entry) => entry,
+                Err(_) => return WalkState::Skip,
+            };
+
+            // Fetched once and threaded through to `Node::new` below so the cross-device check
+            // and the node's own size/inode fields don't each stat the entry separately.
+            let metadata = entry.metadata().ok();
+
+            // A boundary directory (one that lives on a different filesystem than the root) is
+            // still shown, like dua does, so the output doesn't pretend the mount point doesn't
+            // exist; only descent into it is suppressed.
+            let crosses_device = root_dev
+                .map(|root_dev| {
+                    #[cfg(unix)]
+                    { metadata.as_ref().map(|md| md.dev() != root_dev).unwrap_or(false) }
+
+                    #[cfg(not(unix))]
+                    { false }
+                })
+                .unwrap_or(false);
+
+            tx.send(Node::new(entry, metadata, disk_usage)).unwrap();
+
+            if crosses_device {
+                WalkState::Skip
+            } else {
+                WalkState::Continue
+            }
         }));
 
         drop(tx);
 
         let (mut root, mut branches) = tree_components.join().unwrap()?;
 
-        Self::assemble_tree(&mut root, &mut branches, order);
+        if let Some(progress) = &progress {
+            progress.clear();
+        }
+
+        let mut seen_inodes = HashMap::new();
+
+        Self::assemble_tree(&mut root, &mut branches, order, &mut seen_inodes, count_hard_links);
 
         Ok(root)
     }
 
     /// Takes the results of the parallel traversal and uses it to construct the [Tree] data
     /// structure. Sorting occurs if specified.
-    fn assemble_tree(current_dir: &mut Node, branches: &mut Branches, order: &Order) {
+    ///
+    /// `seen_inodes` is shared across the entire recursion (not reset per-directory) so that a
+    /// hard link spanning two sibling directories is still only folded into the aggregated size
+    /// once, unless `count_hard_links` is set.
+    fn assemble_tree(
+        current_dir: &mut Node,
+        branches: &mut Branches,
+        order: &Order,
+        seen_inodes: &mut HashMap<(u64, u64), u64>,
+        count_hard_links: bool,
+    ) {
         let dir_node = branches.remove(current_dir.path())
             .and_then(|children| {
                 current_dir.set_children(children);
@@ -132,18 +224,21 @@ impl Tree {
 
         if let Some(node) = dir_node {
             let mut dir_size = 0;
+            let mut entry_count = 0;
 
             node.children_mut()
                 .map(|nodes| nodes.iter_mut())
                 .map(|node_iter| {
                     node_iter.for_each(|node| {
                         if node.is_dir() {
-                            Self::assemble_tree(node, branches, order);
+                            Self::assemble_tree(node, branches, order, seen_inodes, count_hard_links);
                         }
-                        dir_size += node.file_size.unwrap_or(0);
+                        dir_size += Self::size_contribution(node, seen_inodes, count_hard_links);
+                        entry_count += 1 + node.entry_count.unwrap_or(0);
                     });
                 });
 
+            node.set_entry_count(entry_count);
             if dir_size > 0 { node.set_file_size(dir_size) }
 
             order
@@ -154,6 +249,71 @@ impl Tree {
                 });
         }
     }
+
+    /// Returns how much of `node`'s size should be folded into its parent's aggregated total.
+    ///
+    /// Unless `count_hard_links` is set, a node whose inode has more than one hard link only
+    /// contributes its size the first time that inode is encountered anywhere in the tree;
+    /// later encounters contribute zero so directory sizes don't double-count shared inodes.
+    fn size_contribution(
+        node: &Node,
+        seen_inodes: &mut HashMap<(u64, u64), u64>,
+        count_hard_links: bool,
+    ) -> u64 {
+        let size = node.file_size.unwrap_or(0);
+
+        if count_hard_links || !node.is_hard_link() {
+            return size;
+        }
+
+        match node.inode {
+            Some(inode) => {
+                let occurrences = seen_inodes.entry(inode).or_insert(0);
+                *occurrences += 1;
+
+                if *occurrences > 1 { 0 } else { size }
+            }
+            None => size,
+        }
+    }
+
+    /// Walks the already-assembled tree attaching each entry's git status, looked up by path.
+    /// Pure post-processing: traversal has already finished by the time this runs, so no stat
+    /// syscalls happen here.
+    ///
+    /// `statuses` is keyed by path relative to the repo's workdir, while `node.path()` is
+    /// relative to `walker_root` (i.e. `ctx.dir()`, e.g. `./src/main.rs` when it defaults to
+    /// `.`). `repo_relative_root` is the (already-computed) path from the workdir down to
+    /// `walker_root`, so rebasing a node's path onto the keys `statuses` uses is just:
+    /// `repo_relative_root.join(node.path().strip_prefix(walker_root))`.
+    ///
+    /// Sorting in [`Tree::assemble_tree`] runs before any status is attached, so when `order` is
+    /// [`Order::GitChanges`] each node's children are sorted again here once annotated.
+    fn annotate_git_status(
+        node: &mut Node,
+        walker_root: &Path,
+        repo_relative_root: &Path,
+        statuses: &HashMap<PathBuf, GitStatus>,
+        order: &Order,
+    ) {
+        if let Ok(relative) = node.path().strip_prefix(walker_root) {
+            let key = repo_relative_root.join(relative);
+
+            if let Some(status) = statuses.get(&key) {
+                node.set_git_status(*status);
+            }
+        }
+
+        node.children_mut().map(|children| {
+            children
+                .iter_mut()
+                .for_each(|child| Self::annotate_git_status(child, walker_root, repo_relative_root, statuses, order));
+
+            if let (Order::GitChanges, Some(func)) = (order, order.comparator()) {
+                children.sort_by(func);
+            }
+        });
+    }
 }
 
 impl Display for Tree {
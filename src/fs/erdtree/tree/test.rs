@@ -0,0 +1,99 @@
+use super::Tree;
+use crate::{context::Context, fs::erdtree::git::GitStatus};
+use clap::Parser;
+use git2::Repository;
+use std::{fs, sync::Mutex};
+
+/// Guards tests that call `std::env::set_current_dir`, since the current directory is
+/// process-wide state shared across concurrently-running tests.
+static CWD_LOCK: Mutex<()> = Mutex::new(());
+
+/// Builds a throwaway directory under the system temp dir for a single test to populate.
+fn scratch_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("erdtree-test-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn dedups_hard_linked_file_sizes_by_default() {
+    let dir = scratch_dir("hardlink-dedup");
+
+    fs::write(dir.join("original.txt"), vec![0u8; 4096]).unwrap();
+    fs::hard_link(dir.join("original.txt"), dir.join("linked.txt")).unwrap();
+    fs::write(dir.join("unrelated.txt"), vec![0u8; 1024]).unwrap();
+
+    let ctx = Context::parse_from(["erdtree", dir.to_str().unwrap()]);
+    let tree = Tree::new(ctx.walker(), &ctx).unwrap();
+
+    assert_eq!(tree.root().file_size, Some(4096 + 1024));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn count_hard_links_flag_counts_every_link() {
+    let dir = scratch_dir("hardlink-count-flag");
+
+    fs::write(dir.join("original.txt"), vec![0u8; 4096]).unwrap();
+    fs::hard_link(dir.join("original.txt"), dir.join("linked.txt")).unwrap();
+
+    let ctx = Context::parse_from(["erdtree", "--count-hard-links", dir.to_str().unwrap()]);
+    let tree = Tree::new(ctx.walker(), &ctx).unwrap();
+
+    assert_eq!(tree.root().file_size, Some(4096 * 2));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn git_status_is_annotated_for_an_untracked_file() {
+    let dir = scratch_dir("git-status");
+
+    Repository::init(&dir).unwrap();
+    fs::write(dir.join("untracked.txt"), "hello").unwrap();
+
+    let ctx = Context::parse_from(["erdtree", "--git", dir.to_str().unwrap()]);
+    let tree = Tree::new(ctx.walker(), &ctx).unwrap();
+
+    let untracked = tree
+        .root()
+        .children()
+        .unwrap()
+        .find(|child| child.path().file_name().unwrap() == "untracked.txt")
+        .expect("untracked.txt should be present in the tree");
+
+    assert_eq!(untracked.git_status, Some(GitStatus::Untracked));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn git_status_is_annotated_from_the_default_directory() {
+    let _guard = CWD_LOCK.lock().unwrap();
+
+    let dir = scratch_dir("git-status-default-dir");
+
+    Repository::init(&dir).unwrap();
+    fs::write(dir.join("untracked.txt"), "hello").unwrap();
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&dir).unwrap();
+
+    let ctx = Context::parse_from(["erdtree", "--git"]);
+    let tree = Tree::new(ctx.walker(), &ctx).unwrap();
+
+    std::env::set_current_dir(original_dir).unwrap();
+
+    let untracked = tree
+        .root()
+        .children()
+        .unwrap()
+        .find(|child| child.path().file_name().unwrap() == "untracked.txt")
+        .expect("untracked.txt should be present in the tree");
+
+    assert_eq!(untracked.git_status, Some(GitStatus::Untracked));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
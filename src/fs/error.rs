@@ -0,0 +1,44 @@
+use std::{fmt, io};
+
+/// Errors that may occur while constructing or traversing a [Tree].
+///
+/// [Tree]: super::erdtree::tree::Tree
+#[derive(Debug)]
+pub enum Error {
+    /// A non-root directory entry arrived in the traversal channel with no parent path.
+    ExpectedParent,
+
+    /// The root directory entry was never received from the walker.
+    MissingRoot,
+
+    /// Wraps I/O errors encountered while inspecting filesystem entries.
+    IO(io::Error),
+
+    /// Wraps errors surfaced by the `ignore` crate during parallel traversal.
+    Ignore(ignore::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExpectedParent => write!(f, "expected directory entry to have a parent"),
+            Self::MissingRoot => write!(f, "failed to find root directory"),
+            Self::IO(e) => write!(f, "{e}"),
+            Self::Ignore(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::IO(e)
+    }
+}
+
+impl From<ignore::Error> for Error {
+    fn from(e: ignore::Error) -> Self {
+        Self::Ignore(e)
+    }
+}
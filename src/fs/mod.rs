@@ -0,0 +1,2 @@
+pub mod erdtree;
+pub mod error;
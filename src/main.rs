@@ -0,0 +1,27 @@
+mod context;
+mod fs;
+
+use clap::Parser;
+use context::Context;
+use fs::erdtree::tree::Tree;
+use std::{io, process::ExitCode};
+
+fn main() -> ExitCode {
+    let ctx = Context::parse();
+    let walker = ctx.walker();
+
+    let tree = match Tree::new(walker, &ctx) {
+        Ok(tree) => tree,
+        Err(e) => {
+            eprintln!("erdtree: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Err(e) = ctx.output.renderer().render(&tree, &mut io::stdout()) {
+        eprintln!("erdtree: {e}");
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}